@@ -0,0 +1,32 @@
+//! A set of device drivers for 25-series SPI Flash and EEPROM chips.
+#![no_std]
+
+mod series25;
+mod utils;
+
+pub use series25::*;
+
+/// Error type, generic over the wrapped SPI device's error type.
+///
+/// Every fallible operation in this crate returns a `Result<_, Error<SPI>>`, so callers can
+/// match on `Error::Spi` to get at the underlying bus error.
+pub enum Error<SPI: embedded_hal_async::spi::ErrorType> {
+    /// An SPI transfer failed.
+    Spi(SPI::Error),
+    /// `write_bytes` was called with a length incompatible with the device's `BLOCK_LENGTH`.
+    BlockLength,
+    /// An address or length was not aligned to the device's erase granularity.
+    NotAligned,
+}
+
+// Implemented by hand instead of `#[derive(Debug)]`: a derive would add a spurious `SPI: Debug`
+// bound even though only `SPI::Error` is ever stored.
+impl<SPI: embedded_hal_async::spi::ErrorType> core::fmt::Debug for Error<SPI> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Error::Spi(err) => f.debug_tuple("Spi").field(err).finish(),
+            Error::BlockLength => f.write_str("BlockLength"),
+            Error::NotAligned => f.write_str("NotAligned"),
+        }
+    }
+}
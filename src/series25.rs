@@ -4,10 +4,10 @@ use crate::{utils::HexSlice, Error};
 use bitflags::bitflags;
 use core::fmt;
 use core::marker::PhantomData;
-pub use core::task::Poll;
 pub use embedded_hal::digital::OutputPin;
-use embedded_hal::spi::Operation;
-pub use embedded_hal::spi::SpiDevice;
+pub use embedded_hal_async::delay::DelayNs;
+use embedded_hal_async::spi::Operation;
+pub use embedded_hal_async::spi::SpiDevice;
 
 /// 3-Byte JEDEC manufacturer and device identification.
 pub struct Identification {
@@ -87,10 +87,77 @@ enum Opcode {
     /// Write the 8-bit status register. Not all bits are writeable.
     WriteStatus = 0x01,
     Read = 0x03,
+    /// Read at higher clock speeds, at the cost of one dummy byte after the address.
+    FastRead = 0x0B,
     PageProg = 0x02, // directly writes to EEPROMs too
     SectorErase = 0x20,
+    /// Erases a 32 KiB block.
+    Block32Erase = 0x52,
     BlockErase = 0xD8,
     ChipErase = 0xC7,
+    /// Enter 4-byte addressing mode.
+    Enter4ByteAddr = 0xB7,
+    /// Exit 4-byte addressing mode.
+    Exit4ByteAddr = 0xE9,
+    /// Write the bank address register (Spansion-style extended addressing).
+    BankAddrWrite = 0x17,
+}
+
+/// JEDEC manufacturer code for Micron.
+const MFR_MICRON: u8 = 0x20;
+/// JEDEC manufacturer code for Spansion/Cypress.
+const MFR_SPANSION: u8 = 0x01;
+
+/// Size of the 32 KiB block erased by `Opcode::Block32Erase`, as used by [`Flash::erase_range`].
+const BLOCK32_SIZE: usize = 32 * 1024;
+
+/// Erase granularities available on 25-series parts, used by [`Flash::erase_range`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum EraseGranularity {
+    Sector,
+    Block32,
+    Block64,
+}
+
+impl EraseGranularity {
+    fn opcode(self) -> Opcode {
+        match self {
+            EraseGranularity::Sector => Opcode::SectorErase,
+            EraseGranularity::Block32 => Opcode::Block32Erase,
+            EraseGranularity::Block64 => Opcode::BlockErase,
+        }
+    }
+}
+
+/// Picks the largest erase granularity whose size divides `addr` and still fits within
+/// `remaining`, given the device's sector/32K-block/64K-block sizes. Kept as a pure function
+/// (no SPI access) so the selection logic can be unit-tested directly.
+fn select_erase_step(
+    addr: u32,
+    remaining: u32,
+    sector_size: u32,
+    block32_size: u32,
+    block64_size: u32,
+) -> (EraseGranularity, u32) {
+    if addr.is_multiple_of(block64_size) && remaining >= block64_size {
+        (EraseGranularity::Block64, block64_size)
+    } else if addr.is_multiple_of(block32_size) && remaining >= block32_size {
+        (EraseGranularity::Block32, block32_size)
+    } else {
+        (EraseGranularity::Sector, sector_size)
+    }
+}
+
+/// How long to wait between status register polls in [`Flash::wait_done`].
+const WAIT_DONE_POLL_INTERVAL_US: u32 = 100;
+
+/// The addressing mode used when building commands that carry an address.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum AddressWidth {
+    /// 24-bit addresses, as used by all 25-series parts up to 16 MiB.
+    ThreeByte,
+    /// 32-bit addresses, needed to address chips larger than 16 MiB.
+    FourByte,
 }
 
 bitflags! {
@@ -119,23 +186,66 @@ pub trait FlashParameters {
     const CHIP_SIZE: usize;
 }
 
+/// Trait for reading from a memory device.
+///
+/// This is implemented for concrete drivers such as [`Flash`] so that generic code can be
+/// written against it instead of a specific chip type.
+// `async fn` in a public trait can't express a `Send` bound on the returned future, but these
+// drivers run on single-threaded embedded executors where that doesn't matter.
+#[allow(async_fn_in_trait)]
+pub trait Read<Addr, SPI: embedded_hal_async::spi::ErrorType> {
+    /// Reads bytes starting at `addr` into `buf`.
+    async fn read(&mut self, addr: Addr, buf: &mut [u8]) -> Result<(), Error<SPI>>;
+}
+
+/// Trait for memory devices organized into erasable blocks, such as NOR flash and FRAM parts.
+///
+/// Implementing this instead of relying on a concrete chip type lets generic drivers and
+/// filesystems stay agnostic of the specific command set in use.
+// See the note on `Read` above: no `Send` bound is needed for single-threaded embedded use.
+#[allow(async_fn_in_trait)]
+pub trait BlockDevice<Addr, SPI: embedded_hal_async::spi::ErrorType> {
+    /// The write granularity required by [`BlockDevice::write_bytes`]: `data.len()` must be a
+    /// multiple of this.
+    const BLOCK_LENGTH: usize;
+
+    /// Erases a sector from the memory chip.
+    async fn erase_sector(&mut self, addr: Addr) -> Result<(), Error<SPI>>;
+
+    /// Erases a block from the memory chip.
+    async fn erase_block(&mut self, addr: Addr) -> Result<(), Error<SPI>>;
+
+    /// Erases the memory chip fully.
+    async fn erase_all(&mut self) -> Result<(), Error<SPI>>;
+
+    /// Writes bytes onto the memory chip, starting at `addr`.
+    ///
+    /// Returns [`Error::BlockLength`] if `data.len()` is not a multiple of `BLOCK_LENGTH`.
+    async fn write_bytes(&mut self, addr: Addr, data: &[u8]) -> Result<(), Error<SPI>>;
+}
+
 /// Driver for 25-series SPI Flash chips.
 ///
 /// # Type Parameters
 ///
-/// * **`SPI`**: The SPI master to which the flash chip is attached.
+/// * **`SPI`**: The async SPI master to which the flash chip is attached.
+/// * **`DELAY`**: An async delay, used to poll the status register without busy-looping while
+///   an erase or program operation is in progress.
 #[derive(Debug)]
-pub struct Flash<SPI, FlashParams>
+pub struct Flash<SPI, DELAY, FlashParams>
 where
     FlashParams: FlashParameters,
 {
     spi: SPI,
+    delay: DELAY,
     params: PhantomData<FlashParams>,
+    address_width: AddressWidth,
 }
 
-impl<SPI, FlashParams> Flash<SPI, FlashParams>
+impl<SPI, DELAY, FlashParams> Flash<SPI, DELAY, FlashParams>
 where
     SPI: SpiDevice<u8>,
+    DELAY: DelayNs,
     FlashParams: FlashParameters,
 {
     /// Creates a new 26-series flash driver.
@@ -144,14 +254,21 @@ where
     ///
     /// * **`spi`**: An SPI master. Must be configured to operate in the correct
     ///   mode for the device.
-    pub fn init(spi: SPI, _params: FlashParams) -> Result<Flash<SPI, FlashParams>, Error<SPI>> {
+    /// * **`delay`**: An async delay, used while waiting for erase/program operations to finish.
+    pub async fn init(
+        spi: SPI,
+        delay: DELAY,
+        _params: FlashParams,
+    ) -> Result<Flash<SPI, DELAY, FlashParams>, Error<SPI>> {
         let mut this = Flash {
             spi,
+            delay,
             params: PhantomData,
+            address_width: AddressWidth::ThreeByte,
         };
 
         // If the MCU is reset and an old operation is still ongoing, wait for it to finish.
-        this.wait_done()?;
+        this.wait_done().await?;
 
         Ok(this)
     }
@@ -176,52 +293,160 @@ where
         FlashParams::CHIP_SIZE
     }
 
-    fn command_transfer(&mut self, bytes: &mut [u8]) -> Result<(), Error<SPI>> {
-        self.spi.transfer_in_place(bytes).map_err(Error::Spi)
+    async fn command_transfer(&mut self, bytes: &mut [u8]) -> Result<(), Error<SPI>> {
+        self.spi.transfer_in_place(bytes).await.map_err(Error::Spi)
+    }
+
+    async fn command_write(&mut self, bytes: &[u8]) -> Result<(), Error<SPI>> {
+        self.spi.write(bytes).await.map_err(Error::Spi)
+    }
+
+    /// Builds an opcode + address command buffer, using 3 or 4 address bytes depending on the
+    /// currently active addressing mode. Returns the buffer along with the number of leading
+    /// bytes that are actually in use.
+    fn command_buf(&self, opcode: Opcode, addr: u32) -> ([u8; 5], usize) {
+        match self.address_width {
+            AddressWidth::ThreeByte => (
+                [
+                    opcode as u8,
+                    (addr >> 16) as u8,
+                    (addr >> 8) as u8,
+                    addr as u8,
+                    0,
+                ],
+                4,
+            ),
+            AddressWidth::FourByte => (
+                [
+                    opcode as u8,
+                    (addr >> 24) as u8,
+                    (addr >> 16) as u8,
+                    (addr >> 8) as u8,
+                    addr as u8,
+                ],
+                5,
+            ),
+        }
+    }
+
+    /// Enables 4-byte addressing mode, allowing access to the full range of chips larger than
+    /// 16 MiB.
+    ///
+    /// The enable sequence is manufacturer-specific, so this reads back the JEDEC ID to pick the
+    /// right one: Micron parts require `WREN` before `EN4B`, Macronix and Winbond parts accept
+    /// `EN4B` directly, and Spansion-style parts instead set the extended-address-enable bit in
+    /// their bank address register via opcode `0x17`.
+    pub async fn enter_4byte_mode(&mut self) -> Result<(), Error<SPI>> {
+        let mfr_code = self.read_jedec_id().await?.mfr_code();
+
+        match mfr_code {
+            MFR_MICRON => {
+                self.write_enable().await?;
+                self.command_write(&[Opcode::Enter4ByteAddr as u8]).await?;
+            }
+            MFR_SPANSION => {
+                self.command_write(&[Opcode::BankAddrWrite as u8, 0x80])
+                    .await?;
+            }
+            // Macronix (0xC2), Winbond (0xEF) and all other manufacturers accept EN4B directly.
+            _ => self.command_write(&[Opcode::Enter4ByteAddr as u8]).await?,
+        }
+
+        self.address_width = AddressWidth::FourByte;
+        Ok(())
     }
 
-    fn command_write(&mut self, bytes: &[u8]) -> Result<(), Error<SPI>> {
-        self.spi.write(bytes).map_err(Error::Spi)
+    /// Exits 4-byte addressing mode, returning to 24-bit addresses.
+    pub async fn exit_4byte_mode(&mut self) -> Result<(), Error<SPI>> {
+        let mfr_code = self.read_jedec_id().await?.mfr_code();
+
+        match mfr_code {
+            MFR_MICRON => {
+                self.write_enable().await?;
+                self.command_write(&[Opcode::Exit4ByteAddr as u8]).await?;
+            }
+            MFR_SPANSION => {
+                self.command_write(&[Opcode::BankAddrWrite as u8, 0x00])
+                    .await?;
+            }
+            // Macronix (0xC2), Winbond (0xEF) and all other manufacturers accept EX4B directly.
+            _ => self.command_write(&[Opcode::Exit4ByteAddr as u8]).await?,
+        }
+
+        self.address_width = AddressWidth::ThreeByte;
+        Ok(())
     }
 
     /// Reads the JEDEC manufacturer/device identification.
-    pub fn read_jedec_id(&mut self) -> Result<Identification, Error<SPI>> {
+    pub async fn read_jedec_id(&mut self) -> Result<Identification, Error<SPI>> {
         // Optimistically read 12 bytes, even though some identifiers will be shorter
         let mut buf: [u8; 12] = [0; 12];
         buf[0] = Opcode::ReadJedecId as u8;
-        self.command_transfer(&mut buf)?;
+        self.command_transfer(&mut buf).await?;
 
         // Skip buf[0] (SPI read response byte)
         Ok(Identification::from_jedec_id(&buf[1..]))
     }
 
     /// Reads the status register.
-    pub fn read_status(&mut self) -> Result<Status, Error<SPI>> {
+    pub async fn read_status(&mut self) -> Result<Status, Error<SPI>> {
         let mut buf = [Opcode::ReadStatus as u8, 0];
-        self.command_transfer(&mut buf)?;
+        self.command_transfer(&mut buf).await?;
 
         Ok(Status::from_bits_truncate(buf[1]))
     }
 
-    fn write_enable(&mut self) -> Result<(), Error<SPI>> {
-        let cmd_buf = [Opcode::WriteEnable as u8];
-        self.command_write(&cmd_buf)
+    /// Writes the status register, preceded by a `WREN`, and waits for the write to complete.
+    ///
+    /// Not all bits are writeable this way; refer to your device's datasheet for which ones
+    /// stick.
+    pub async fn write_status(&mut self, status: Status) -> Result<(), Error<SPI>> {
+        self.write_enable().await?;
+        let cmd_buf = [Opcode::WriteStatus as u8, status.bits()];
+        self.command_write(&cmd_buf).await?;
+        self.wait_done().await
     }
 
-    pub fn wait_done(&mut self) -> Result<(), Error<SPI>> {
-        while self.read_status()?.contains(Status::BUSY) {}
-        Ok(())
+    /// Sets the 3-bit `PROT` field to `regions` (`0..=7`, as defined by the device's datasheet;
+    /// larger values protect a larger region of the address space from program/erase).
+    pub async fn protect_regions(&mut self, regions: u8) -> Result<(), Error<SPI>> {
+        let mut status = self.read_status().await?;
+        status.remove(Status::PROT);
+        status.insert(Status::from_bits_truncate((regions << 2) & Status::PROT.bits()));
+        self.write_status(status).await
     }
 
-    pub fn poll_wait_done(&mut self) -> Poll<()> {
-        // TODO: Consider changing this to a delay based pattern
-        let status = self.read_status().unwrap_or(Status::BUSY);
+    /// Clears the `PROT` field, removing write protection from the whole chip.
+    pub async fn unprotect_all(&mut self) -> Result<(), Error<SPI>> {
+        let mut status = self.read_status().await?;
+        status.remove(Status::PROT);
+        self.write_status(status).await
+    }
 
-        if status.contains(Status::BUSY) {
-            Poll::Pending
+    /// Sets or clears the `SRWD` bit, which locks the status register against further writes
+    /// (subject to the hardware `WP#` pin state).
+    pub async fn set_srwd(&mut self, enable: bool) -> Result<(), Error<SPI>> {
+        let mut status = self.read_status().await?;
+        if enable {
+            status.insert(Status::SRWD);
         } else {
-            Poll::Ready(())
+            status.remove(Status::SRWD);
         }
+        self.write_status(status).await
+    }
+
+    async fn write_enable(&mut self) -> Result<(), Error<SPI>> {
+        let cmd_buf = [Opcode::WriteEnable as u8];
+        self.command_write(&cmd_buf).await
+    }
+
+    /// Waits for the current erase/program operation to finish, polling the status register
+    /// with an awaited delay between polls instead of busy-looping.
+    pub async fn wait_done(&mut self) -> Result<(), Error<SPI>> {
+        while self.read_status().await?.contains(Status::BUSY) {
+            self.delay.delay_us(WAIT_DONE_POLL_INTERVAL_US).await;
+        }
+        Ok(())
     }
 
     /// Reads flash contents into `buf`, starting at `addr`.
@@ -229,25 +454,47 @@ where
     /// Note that `addr` is not fully decoded: Flash chips will typically only
     /// look at the lowest `N` bits needed to encode their size, which means
     /// that the contents are "mirrored" to addresses that are a multiple of the
-    /// flash size. Only 24 bits of `addr` are transferred to the device in any
-    /// case, limiting the maximum size of 25-series SPI flash chips to 16 MiB.
+    /// flash size. By default only 24 bits of `addr` are transferred to the device,
+    /// limiting the maximum size of 25-series SPI flash chips to 16 MiB; call
+    /// [`Flash::enter_4byte_mode`] first to address chips larger than that.
     ///
     /// # Parameters
     ///
-    /// * `addr`: 24-bit address to start reading at.
+    /// * `addr`: 24-bit (or 32-bit, in 4-byte addressing mode) address to start reading at.
     /// * `buf`: Destination buffer to fill.
-    pub fn read(&mut self, addr: u32, buf: &mut [u8]) -> Result<(), Error<SPI>> {
+    pub async fn read(&mut self, addr: u32, buf: &mut [u8]) -> Result<(), Error<SPI>> {
         // TODO what happens if `buf` is empty?
 
-        let cmd_buf = [
-            Opcode::Read as u8,
-            (addr >> 16) as u8,
-            (addr >> 8) as u8,
-            addr as u8,
-        ];
+        let (cmd_buf, len) = self.command_buf(Opcode::Read, addr);
+
+        self.spi
+            .transaction(&mut [Operation::Write(&cmd_buf[..len]), Operation::Read(buf)])
+            .await
+            .map_err(Error::Spi)
+    }
+
+    /// Reads flash contents into `buf` using the Fast Read command, starting at `addr`.
+    ///
+    /// This sends the opcode and address followed by one dummy byte before the chip starts
+    /// clocking out data, which lets the bus run well above the ~20-50 MHz ceiling of the plain
+    /// [`Flash::read`] command. Not all parts support Fast Read; for those that don't, use
+    /// `read` instead.
+    ///
+    /// # Parameters
+    ///
+    /// * `addr`: 24-bit (or 32-bit, in 4-byte addressing mode) address to start reading at.
+    /// * `buf`: Destination buffer to fill.
+    pub async fn read_fast(&mut self, addr: u32, buf: &mut [u8]) -> Result<(), Error<SPI>> {
+        let (addr_buf, addr_len) = self.command_buf(Opcode::FastRead, addr);
+
+        let mut cmd_buf = [0u8; 6];
+        cmd_buf[..addr_len].copy_from_slice(&addr_buf[..addr_len]);
+        // cmd_buf[addr_len] stays 0: the dummy byte clocked in before data starts.
+        let cmd_len = addr_len + 1;
 
         self.spi
-            .transaction(&mut [Operation::Write(&cmd_buf), Operation::Read(buf)])
+            .transaction(&mut [Operation::Write(&cmd_buf[..cmd_len]), Operation::Read(buf)])
+            .await
             .map_err(Error::Spi)
     }
 
@@ -256,17 +503,12 @@ where
     /// # Parameters
     /// * `addr`: The address to start erasing at. If the address is not on a sector boundary,
     ///   the lower bits can be ignored in order to make it fit.
-    pub fn erase_sector(mut self, addr: u32) -> Result<(), Error<SPI>> {
-        self.write_enable()?;
-
-        let cmd_buf = [
-            Opcode::SectorErase as u8,
-            (addr >> 16) as u8,
-            (addr >> 8) as u8,
-            addr as u8,
-        ];
-        self.command_write(&cmd_buf)?;
-        self.wait_done()
+    pub async fn erase_sector(&mut self, addr: u32) -> Result<(), Error<SPI>> {
+        self.write_enable().await?;
+
+        let (cmd_buf, len) = self.command_buf(Opcode::SectorErase, addr);
+        self.command_write(&cmd_buf[..len]).await?;
+        self.wait_done().await
     }
 
     /// Erases a block from the memory chip.
@@ -274,55 +516,138 @@ where
     /// # Parameters
     /// * `addr`: The address to start erasing at. If the address is not on a block boundary,
     ///   the lower bits can be ignored in order to make it fit.
-    pub fn erase_block(mut self, addr: u32) -> Result<(), Error<SPI>> {
-        self.write_enable()?;
-
-        let cmd_buf = [
-            Opcode::BlockErase as u8,
-            (addr >> 16) as u8,
-            (addr >> 8) as u8,
-            addr as u8,
-        ];
-        self.command_write(&cmd_buf)?;
-        self.wait_done()
+    pub async fn erase_block(&mut self, addr: u32) -> Result<(), Error<SPI>> {
+        self.write_enable().await?;
+
+        let (cmd_buf, len) = self.command_buf(Opcode::BlockErase, addr);
+        self.command_write(&cmd_buf[..len]).await?;
+        self.wait_done().await
+    }
+
+    /// Erases a range of the memory chip, greedily picking the largest erase granularity
+    /// (64 KiB block, 32 KiB block, or 4 KiB sector) that divides the current address and still
+    /// fits within what's left to erase. This minimizes the number of erase commands issued
+    /// compared to erasing the whole range sector by sector.
+    ///
+    /// # Parameters
+    /// * `addr`: The address to start erasing at. Must be aligned to `SECTOR_SIZE`.
+    /// * `len`: The number of bytes to erase. Must be a multiple of `SECTOR_SIZE`.
+    pub async fn erase_range(&mut self, addr: u32, len: u32) -> Result<(), Error<SPI>> {
+        let sector_size = FlashParams::SECTOR_SIZE as u32;
+        let block32_size = BLOCK32_SIZE as u32;
+        let block64_size = FlashParams::BLOCK_SIZE as u32;
+
+        if !addr.is_multiple_of(sector_size) || !len.is_multiple_of(sector_size) {
+            return Err(Error::NotAligned);
+        }
+
+        let mut addr = addr;
+        let mut remaining = len;
+
+        while remaining > 0 {
+            let (granularity, step) =
+                select_erase_step(addr, remaining, sector_size, block32_size, block64_size);
+
+            self.write_enable().await?;
+            let (cmd_buf, cmd_len) = self.command_buf(granularity.opcode(), addr);
+            self.command_write(&cmd_buf[..cmd_len]).await?;
+            self.wait_done().await?;
+
+            addr += step;
+            remaining -= step;
+        }
+
+        Ok(())
     }
 
     /// Writes bytes onto the memory chip. This method is supposed to assume that the sectors
     /// it is writing to have already been erased and should not do any erasing themselves.
     ///
+    /// `data` may be of arbitrary length. Since a page program only ever wraps around within
+    /// the current `PAGE_SIZE`-aligned page rather than advancing into the next one, `data` is
+    /// split into page-sized (or smaller, for the first/last chunk) pieces and written with one
+    /// page-program command per piece.
+    ///
     /// # Parameters
     /// * `addr`: The address to write to.
-    /// * `data`: The bytes to write to `addr`, note that it will only take the lowest 256 bytes
-    /// from the slice.
-    pub fn write_bytes(mut self, addr: u32, data: &[u8]) -> Result<(), Error<SPI>> {
-        self.write_enable()?;
-
-        let cmd_buf = [
-            Opcode::PageProg as u8,
-            (addr >> 16) as u8,
-            (addr >> 8) as u8,
-            addr as u8,
-        ];
+    /// * `data`: The bytes to write to `addr`.
+    pub async fn write_bytes(&mut self, addr: u32, data: &[u8]) -> Result<(), Error<SPI>> {
+        let mut addr = addr;
+        let mut data = data;
 
-        self.spi
-            .transaction(&mut [
-                Operation::Write(&cmd_buf),
-                Operation::Write(&data[..256.min(data.len())]),
-            ])
-            .map_err(Error::Spi)?;
+        while !data.is_empty() {
+            let page_offset = addr as usize % FlashParams::PAGE_SIZE;
+            let chunk_len = (FlashParams::PAGE_SIZE - page_offset).min(data.len());
+            let (chunk, rest) = data.split_at(chunk_len);
+
+            self.write_enable().await?;
+
+            let (cmd_buf, len) = self.command_buf(Opcode::PageProg, addr);
+
+            self.spi
+                .transaction(&mut [Operation::Write(&cmd_buf[..len]), Operation::Write(chunk)])
+                .await
+                .map_err(Error::Spi)?;
+
+            self.wait_done().await?;
 
-        self.wait_done()
+            addr += chunk_len as u32;
+            data = rest;
+        }
+
+        Ok(())
     }
 
     /// Erases the memory chip fully.
     ///
     /// Warning: Full erase operations can take a significant amount of time.
     /// Check your device's datasheet for precise numbers.
-    pub fn erase_all(mut self) -> Result<(), Error<SPI>> {
-        self.write_enable()?;
+    pub async fn erase_all(&mut self) -> Result<(), Error<SPI>> {
+        self.write_enable().await?;
         let cmd_buf = [Opcode::ChipErase as u8];
-        self.command_write(&cmd_buf)?;
-        self.wait_done()
+        self.command_write(&cmd_buf).await?;
+        self.wait_done().await
+    }
+}
+
+impl<SPI, DELAY, FlashParams> Read<u32, SPI> for Flash<SPI, DELAY, FlashParams>
+where
+    SPI: SpiDevice<u8>,
+    DELAY: DelayNs,
+    FlashParams: FlashParameters,
+{
+    async fn read(&mut self, addr: u32, buf: &mut [u8]) -> Result<(), Error<SPI>> {
+        Flash::read(self, addr, buf).await
+    }
+}
+
+impl<SPI, DELAY, FlashParams> BlockDevice<u32, SPI> for Flash<SPI, DELAY, FlashParams>
+where
+    SPI: SpiDevice<u8>,
+    DELAY: DelayNs,
+    FlashParams: FlashParameters,
+{
+    // `Flash::write_bytes` splits arbitrary-length buffers across page boundaries itself, so
+    // generic callers aren't restricted to page-sized (or page-aligned) writes.
+    const BLOCK_LENGTH: usize = 1;
+
+    async fn erase_sector(&mut self, addr: u32) -> Result<(), Error<SPI>> {
+        Flash::erase_sector(self, addr).await
+    }
+
+    async fn erase_block(&mut self, addr: u32) -> Result<(), Error<SPI>> {
+        Flash::erase_block(self, addr).await
+    }
+
+    async fn erase_all(&mut self) -> Result<(), Error<SPI>> {
+        Flash::erase_all(self).await
+    }
+
+    async fn write_bytes(&mut self, addr: u32, data: &[u8]) -> Result<(), Error<SPI>> {
+        if !data.len().is_multiple_of(Self::BLOCK_LENGTH) {
+            return Err(Error::BlockLength);
+        }
+        Flash::write_bytes(self, addr, data).await
     }
 }
 
@@ -340,4 +665,37 @@ mod tests {
         assert_eq!(device_id[0], 0x22);
         assert_eq!(device_id[1], 0x08);
     }
+
+    #[test]
+    fn test_select_erase_step() {
+        const SECTOR: u32 = 4 * 1024;
+        const BLOCK32: u32 = 32 * 1024;
+        const BLOCK64: u32 = 64 * 1024;
+
+        // (addr, remaining) -> (granularity, step)
+        let cases = [
+            // Start isn't aligned to anything bigger than a sector.
+            (SECTOR, BLOCK64, EraseGranularity::Sector, SECTOR),
+            // Aligned to a 64K block and enough of the range remains to use it.
+            (0, BLOCK64, EraseGranularity::Block64, BLOCK64),
+            // Aligned to a 64K block, but not enough remains for one.
+            (0, BLOCK32, EraseGranularity::Block32, BLOCK32),
+            // Aligned to a 32K block but not a 64K one.
+            (BLOCK32, BLOCK64, EraseGranularity::Block32, BLOCK32),
+            // Tail shorter than a full sector-multiple of either block size.
+            (0, SECTOR, EraseGranularity::Sector, SECTOR),
+            (BLOCK64, SECTOR, EraseGranularity::Sector, SECTOR),
+            // Exact multiple of the largest granularity, repeated over a bigger range.
+            (BLOCK64, 2 * BLOCK64, EraseGranularity::Block64, BLOCK64),
+        ];
+
+        for (addr, remaining, expected_granularity, expected_step) in cases {
+            let (granularity, step) = select_erase_step(addr, remaining, SECTOR, BLOCK32, BLOCK64);
+            assert_eq!(
+                granularity, expected_granularity,
+                "addr={addr}, remaining={remaining}"
+            );
+            assert_eq!(step, expected_step, "addr={addr}, remaining={remaining}");
+        }
+    }
 }
@@ -0,0 +1,19 @@
+//! Small internal helpers shared by the chip drivers.
+
+use core::fmt;
+
+/// Formats a byte slice as a hex string, for use in `Debug` impls.
+pub struct HexSlice<T: AsRef<[u8]>>(pub T);
+
+impl<T: AsRef<[u8]>> fmt::Debug for HexSlice<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("[")?;
+        for (i, byte) in self.0.as_ref().iter().enumerate() {
+            if i != 0 {
+                f.write_str(", ")?;
+            }
+            write!(f, "{:#04X}", byte)?;
+        }
+        f.write_str("]")
+    }
+}